@@ -12,15 +12,15 @@
 //!
 //! ## Example
 //!
-//! ```rust
+//! ```rust,no_run
 //! use file_update_monitor::Monitor;
 //! use std::error::Error;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
 //!     // 创建一个监控器实例，监控当前目录，更新间隔为1秒
-//!     let monitor = Monitor::new("./", 1000, |path| {
-//!         println!("检测到文件变化: {}", path);
+//!     let monitor = Monitor::new("./", 1000, |kind, path| {
+//!         println!("检测到文件变化: {:?} {}", kind, path);
 //!         Ok(())
 //!     });
 //!     
@@ -35,13 +35,99 @@ use futures::{
     channel::mpsc::{channel, Receiver},
     SinkExt, StreamExt,
 };
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Config, Event, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::error;
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{watch, Mutex};
 
 /// Generic result type for error handling
 type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
 
+/// A boxed, backend-agnostic watcher paired with the channel it forwards events on
+type WatcherAndReceiver = (Box<dyn Watcher + Send>, Receiver<notify::Result<Event>>);
+
+/// Drain the paths accumulated during one debounce window into a batch
+///
+/// Repeated writes to the same path only ever occupy one slot in `pending` (the last kind
+/// observed wins), so the batch drained here holds each path exactly once.
+fn drain_batch(pending: &mut HashMap<PathBuf, ChangeKind>) -> Vec<(ChangeKind, String)> {
+    pending
+        .drain()
+        .map(|(path, kind)| (kind, path.to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// Selects which `notify` backend is used to observe filesystem events
+///
+/// `Native` relies on the OS-provided mechanism (inotify, FSEvents, ReadDirectoryChangesW), which
+/// is cheap but does not report changes on some network/virtual filesystems (NFS, SMB, FUSE,
+/// certain container overlays). `Poll` falls back to scanning the watched paths on a fixed
+/// interval, which works everywhere at the cost of higher latency and CPU usage.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum WatchMode {
+    /// Use the OS-native watcher (`RecommendedWatcher`)
+    #[default]
+    Native,
+    /// Poll the filesystem on the given interval (`PollWatcher`)
+    Poll(Duration),
+}
+
+/// Handle returned by [`Monitor::start_with_handle`] that can stop a running monitor
+///
+/// Dropping the handle without calling [`MonitorHandle::stop`] leaves the monitor running;
+/// it only stops when explicitly told to.
+pub struct MonitorHandle {
+    stop_tx: watch::Sender<bool>,
+}
+
+impl MonitorHandle {
+    /// Signal the monitor to drop its watcher and return from its event loop
+    pub fn stop(&self) {
+        let _ = self.stop_tx.send(true);
+    }
+}
+
+/// The kind of filesystem change reported for a path
+///
+/// Derived from `notify::EventKind`. `Other` covers metadata-only changes and anything else
+/// `notify` doesn't map onto one of the more specific variants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// A new file or directory was created
+    Created,
+    /// A file's content was modified
+    Modified,
+    /// A file or directory was removed
+    Removed,
+    /// A file or directory was renamed from `from` to `to`
+    Renamed { from: String, to: String },
+    /// Any other kind of event (e.g. metadata-only changes)
+    Other,
+}
+
+/// A single-path change handler, guarded so it can be shared across the monitoring task
+type SingleHandler = Arc<Mutex<Box<dyn FnMut(ChangeKind, String) -> Result<()> + Send>>>;
+
+/// A batched change handler, guarded so it can be shared across the monitoring task
+type BatchHandler = Arc<Mutex<Box<dyn FnMut(Vec<(ChangeKind, String)>) -> Result<()> + Send>>>;
+
+/// Callback invoked by a `Monitor` when files change
+///
+/// `Single` delivers one call per changed path, as soon as its debounce window elapses.
+/// `Batched` accumulates every distinct path seen during a debounce window and delivers them
+/// together once the window elapses quietly, so a burst of writes to many files triggers one
+/// call instead of many. Handlers are `FnMut`, guarded by a `tokio::sync::Mutex`, so a closure
+/// can mutate captured state directly across invocations instead of wrapping it in its own lock.
+enum OnChangeHandler {
+    Single(SingleHandler),
+    Batched(BatchHandler),
+}
+
 /// Main struct for file monitoring
 pub struct Monitor {
     /// Directory path to monitor
@@ -49,7 +135,15 @@ pub struct Monitor {
     /// Update interval in milliseconds
     update_interval: u64,
     /// Callback function for file changes
-    on_change: Arc<Box<dyn Fn(String) -> Result<()> + Send + Sync>>,
+    on_change: OnChangeHandler,
+    /// Which watcher backend to use
+    mode: WatchMode,
+    /// Restrict events to content modifications only, matching the original filter
+    content_only: bool,
+    /// Additional roots registered via `add_path`, watched alongside `dir`
+    extra_paths: Vec<(PathBuf, RecursiveMode)>,
+    /// Glob patterns whose matching paths are dropped before debouncing
+    ignore: Vec<glob::Pattern>,
 }
 
 impl Monitor {
@@ -66,90 +160,544 @@ impl Monitor {
     /// ```
     /// use file_update_monitor::Monitor;
     ///
-    /// let monitor = Monitor::new("./", 1000, |path| {
-    ///     println!("File changed: {}", path);
+    /// let monitor = Monitor::new("./", 1000, |kind, path| {
+    ///     println!("{:?}: {}", kind, path);
     ///     Ok(())
     /// });
     /// ```
     pub fn new<F>(dir: &str, update_interval: u64, on_change: F) -> Self
     where
-        F: Fn(String) -> Result<()> + Send + Sync + 'static,
+        F: FnMut(ChangeKind, String) -> Result<()> + Send + 'static,
+    {
+        Self::with_mode(dir, update_interval, WatchMode::default(), on_change)
+    }
+
+    /// Create a new monitor instance with an explicit watcher backend
+    ///
+    /// Use `WatchMode::Poll` when watching a directory on a network or virtual filesystem where
+    /// the native backend silently fails to deliver events.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory path to monitor
+    /// * `update_interval` - Update interval in milliseconds
+    /// * `mode` - Watcher backend to use
+    /// * `on_change` - Callback function called when files change
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use file_update_monitor::{Monitor, WatchMode};
+    /// use std::time::Duration;
+    ///
+    /// let monitor = Monitor::with_mode("./", 1000, WatchMode::Poll(Duration::from_secs(2)), |kind, path| {
+    ///     println!("{:?}: {}", kind, path);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn with_mode<F>(dir: &str, update_interval: u64, mode: WatchMode, on_change: F) -> Self
+    where
+        F: FnMut(ChangeKind, String) -> Result<()> + Send + 'static,
     {
         Self {
             dir: dir.to_string(),
             update_interval,
-            on_change: Arc::new(Box::new(on_change)),
+            on_change: OnChangeHandler::Single(Arc::new(Mutex::new(Box::new(on_change)))),
+            mode,
+            content_only: false,
+            extra_paths: Vec::new(),
+            ignore: Vec::new(),
         }
     }
 
+    /// Restrict reported events to file content modifications only
+    ///
+    /// Restores the original, narrower filter: creations, removals, renames, and metadata-only
+    /// changes are dropped before they ever reach `on_change`, which is always called with
+    /// `ChangeKind::Modified`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use file_update_monitor::Monitor;
+    ///
+    /// let monitor = Monitor::new("./", 1000, |kind, path| {
+    ///     println!("{:?}: {}", kind, path);
+    ///     Ok(())
+    /// })
+    /// .content_only();
+    /// ```
+    pub fn content_only(mut self) -> Self {
+        self.content_only = true;
+        self
+    }
+
+    /// Create a new monitor instance that batches and de-duplicates changed paths
+    ///
+    /// Instead of firing once per changed path, every distinct path seen within one debounce
+    /// window is collected, keyed by path, and delivered as a single `Vec<(ChangeKind, String)>`
+    /// once the window elapses quietly; the last kind observed for a path wins. This avoids flooding `on_change` when a tool rewrites many files at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory path to monitor
+    /// * `update_interval` - Debounce window in milliseconds
+    /// * `on_change` - Callback function called with the de-duplicated paths that changed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use file_update_monitor::Monitor;
+    ///
+    /// let monitor = Monitor::new_batched("./", 1000, |changes| {
+    ///     println!("Files changed: {:?}", changes);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new_batched<F>(dir: &str, update_interval: u64, on_change: F) -> Self
+    where
+        F: FnMut(Vec<(ChangeKind, String)>) -> Result<()> + Send + 'static,
+    {
+        Self {
+            dir: dir.to_string(),
+            update_interval,
+            on_change: OnChangeHandler::Batched(Arc::new(Mutex::new(Box::new(on_change)))),
+            mode: WatchMode::default(),
+            content_only: false,
+            extra_paths: Vec::new(),
+            ignore: Vec::new(),
+        }
+    }
+
+    /// Register an additional root to watch on this monitor's watcher instance
+    ///
+    /// Use this to monitor several unrelated roots (e.g. multiple workspace packages) without
+    /// spawning one `Monitor` per directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use file_update_monitor::Monitor;
+    /// use notify::RecursiveMode;
+    ///
+    /// let monitor = Monitor::new("./crate-a", 1000, |kind, path| {
+    ///     println!("{:?}: {}", kind, path);
+    ///     Ok(())
+    /// })
+    /// .add_path("./crate-b", RecursiveMode::Recursive);
+    /// ```
+    pub fn add_path(mut self, path: &str, recursive_mode: RecursiveMode) -> Self {
+        self.extra_paths.push((PathBuf::from(path), recursive_mode));
+        self
+    }
+
+    /// Ignore paths matching any of the given glob patterns
+    ///
+    /// Patterns are compiled once, up front, and applied inside the event filter so noise from
+    /// directories like `target/`, `.git/`, or `node_modules/` never reaches `on_change`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use file_update_monitor::Monitor;
+    ///
+    /// let monitor = Monitor::new("./", 1000, |kind, path| {
+    ///     println!("{:?}: {}", kind, path);
+    ///     Ok(())
+    /// })
+    /// .ignore(&["**/target/**", "**/.git/**", "**/node_modules/**"])
+    /// .unwrap();
+    /// ```
+    pub fn ignore(mut self, patterns: &[&str]) -> Result<Self> {
+        for pattern in patterns {
+            self.ignore.push(glob::Pattern::new(pattern)?);
+        }
+        Ok(self)
+    }
+
     /// Start file monitoring
     ///
     /// This is an async method that will continue monitoring the specified directory until program termination
     pub async fn start(&self) {
-        if let Err(e) = self.watch_directory().await {
+        let (_stop_tx, stop_rx) = watch::channel(false);
+        if let Err(e) = self.watch_directory(stop_rx).await {
             eprintln!("File monitoring error: {:?}", e);
         }
     }
 
+    /// Start file monitoring in the background, returning a handle that can stop it
+    ///
+    /// Unlike `start`, which runs until the process exits, this spawns the monitoring loop onto
+    /// the current tokio runtime and returns immediately. Calling `MonitorHandle::stop` causes
+    /// the loop to drop its watcher and return `Ok(())`. This relies on `create_watcher` boxing
+    /// the watcher as `Send` so the spawned future can hold it across an `.await`.
+    pub fn start_with_handle(self) -> MonitorHandle {
+        let (stop_tx, stop_rx) = watch::channel(false);
+
+        tokio::spawn(async move {
+            if let Err(e) = self.watch_directory(stop_rx).await {
+                eprintln!("File monitoring error: {:?}", e);
+            }
+        });
+
+        MonitorHandle { stop_tx }
+    }
+
     /// Internal method: implements core directory monitoring logic
-    async fn watch_directory(&self) -> Result<()> {
+    async fn watch_directory(&self, stop_rx: watch::Receiver<bool>) -> Result<()> {
+        match &self.on_change {
+            OnChangeHandler::Single(on_change) => {
+                self.watch_directory_single(on_change.clone(), stop_rx).await
+            }
+            OnChangeHandler::Batched(on_change) => {
+                self.watch_directory_batched(on_change.clone(), stop_rx).await
+            }
+        }
+    }
+
+    /// Deliver one debounced call per changed path
+    async fn watch_directory_single(
+        &self,
+        on_change: SingleHandler,
+        mut stop_rx: watch::Receiver<bool>,
+    ) -> Result<()> {
         let delay = Duration::from_millis(self.update_interval);
-        let on_change = self.on_change.clone();
-        let debouncer = EventDebouncer::new(delay, move |path: String| on_change(path).unwrap());
+        let debouncer = EventDebouncer::new(delay, move |(kind, path): (ChangeKind, String)| {
+            futures::executor::block_on(async {
+                let mut on_change = on_change.lock().await;
+                on_change(kind, path).unwrap()
+            })
+        });
 
         let (mut watcher, mut rx) = self.create_watcher()?;
-        watcher.watch(Path::new(&self.dir), RecursiveMode::Recursive)?;
+        self.watch_all(watcher.as_mut())?;
+
+        let mut pending_renames: HashMap<usize, String> = HashMap::new();
 
-        while let Some(res) = rx.next().await {
-            match res {
-                Ok(event) => {
-                    if let Some(path) = self.get_valid_path(event) {
-                        debouncer.put(path);
+        loop {
+            tokio::select! {
+                res = rx.next() => match res {
+                    Some(Ok(event)) => {
+                        if let Some((kind, path)) = self.get_valid_path(event, &mut pending_renames) {
+                            debouncer.put((kind, path));
+                        }
+                    }
+                    Some(Err(e)) => eprintln!("File monitoring error: {:?}", e),
+                    None => break,
+                },
+                Ok(()) = stop_rx.changed() => {
+                    if *stop_rx.borrow() {
+                        break;
                     }
                 }
-                Err(e) => eprintln!("File monitoring error: {:?}", e),
             }
         }
 
         Ok(())
     }
 
-    /// Create filesystem event watcher
-    fn create_watcher(
+    /// Accumulate every path seen within one debounce window and deliver them as a batch
+    ///
+    /// The first path observed after a quiet period starts the window; every subsequent path
+    /// resets it via `tokio::select!` racing the event stream against `tokio::time::sleep`. When
+    /// the sleep wins, the accumulated set is drained and delivered in one call.
+    async fn watch_directory_batched(
         &self,
-    ) -> notify::Result<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
-        let (mut tx, rx) = channel(1);
+        on_change: BatchHandler,
+        mut stop_rx: watch::Receiver<bool>,
+    ) -> Result<()> {
+        let delay = Duration::from_millis(self.update_interval);
+
+        let (mut watcher, mut rx) = self.create_watcher()?;
+        self.watch_all(watcher.as_mut())?;
+
+        let mut pending: HashMap<PathBuf, ChangeKind> = HashMap::new();
+        let mut pending_renames: HashMap<usize, String> = HashMap::new();
 
-        let watcher = RecommendedWatcher::new(
-            move |res| {
-                futures::executor::block_on(async {
-                    if let Err(e) = tx.send(res).await {
-                        eprintln!("Error sending event: {:?}", e);
+        loop {
+            if pending.is_empty() {
+                tokio::select! {
+                    res = rx.next() => match res {
+                        Some(Ok(event)) => {
+                            if let Some((kind, path)) = self.get_valid_path(event, &mut pending_renames) {
+                                pending.insert(PathBuf::from(path), kind);
+                            }
+                        }
+                        Some(Err(e)) => eprintln!("File monitoring error: {:?}", e),
+                        None => break,
+                    },
+                    Ok(()) = stop_rx.changed() => {
+                        if *stop_rx.borrow() {
+                            break;
+                        }
                     }
-                })
-            },
-            Config::default(),
-        )?;
+                }
+                continue;
+            }
+
+            tokio::select! {
+                res = rx.next() => match res {
+                    Some(Ok(event)) => {
+                        if let Some((kind, path)) = self.get_valid_path(event, &mut pending_renames) {
+                            pending.insert(PathBuf::from(path), kind);
+                        }
+                    }
+                    Some(Err(e)) => eprintln!("File monitoring error: {:?}", e),
+                    None => break,
+                },
+                _ = tokio::time::sleep(delay) => {
+                    let changes = drain_batch(&mut pending);
+                    let mut on_change = on_change.lock().await;
+                    if let Err(e) = on_change(changes) {
+                        eprintln!("File monitoring error: {:?}", e);
+                    }
+                }
+                Ok(()) = stop_rx.changed() => {
+                    if *stop_rx.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Register every configured root (the primary `dir` plus any via `add_path`) on one watcher
+    fn watch_all(&self, watcher: &mut (dyn Watcher + Send)) -> notify::Result<()> {
+        watcher.watch(Path::new(&self.dir), RecursiveMode::Recursive)?;
+        for (path, recursive_mode) in &self.extra_paths {
+            watcher.watch(path, *recursive_mode)?;
+        }
+        Ok(())
+    }
+
+    /// Create filesystem event watcher
+    ///
+    /// Returns the watcher behind a `Box<dyn Watcher + Send>` so the rest of `watch_directory`
+    /// stays backend-agnostic, regardless of which `WatchMode` was selected. The `Send` bound is
+    /// required because the watcher is held across `.await` points inside tasks spawned by
+    /// `start_with_handle`.
+    fn create_watcher(&self) -> notify::Result<WatcherAndReceiver> {
+        let (mut tx, rx) = channel(1);
+        let handler = move |res| {
+            futures::executor::block_on(async {
+                if let Err(e) = tx.send(res).await {
+                    eprintln!("Error sending event: {:?}", e);
+                }
+            })
+        };
+
+        let watcher: Box<dyn Watcher + Send> = match self.mode {
+            WatchMode::Native => Box::new(RecommendedWatcher::new(handler, Config::default())?),
+            WatchMode::Poll(interval) => Box::new(PollWatcher::new(
+                handler,
+                Config::default().with_poll_interval(interval),
+            )?),
+        };
 
         Ok((watcher, rx))
     }
 
-    /// Extract valid file path from filesystem event
+    /// Extract a `(ChangeKind, path)` pair from a filesystem event
     ///
-    /// Only processes file content modification events, ignores other types of events
-    fn get_valid_path(&self, event: Event) -> Option<String> {
-        if !matches!(
-            event.kind,
-            notify::EventKind::Modify(notify::event::ModifyKind::Data(
-                notify::event::DataChange::Content
-            ))
-        ) {
+    /// When `content_only` is set, restores the original behavior: only content modification
+    /// events are reported, everything else is dropped. Otherwise every event kind is classified
+    /// into a `ChangeKind`, so creations, removals, and renames are surfaced too. Either way,
+    /// paths matching an `ignore` pattern are dropped before they reach the caller.
+    ///
+    /// Renames are reported as a single `ChangeKind::Renamed { from, to }`. Some backends (e.g.
+    /// `notify`'s `RecommendedWatcher` on Linux, backed by inotify) never emit a combined
+    /// `RenameMode::Both` event; instead they emit a separate `From` then `To` event sharing a
+    /// tracker cookie. `pending_renames` coalesces that pair across calls, keyed by cookie.
+    fn get_valid_path(
+        &self,
+        event: Event,
+        pending_renames: &mut HashMap<usize, String>,
+    ) -> Option<(ChangeKind, String)> {
+        if self.content_only {
+            if !matches!(
+                event.kind,
+                notify::EventKind::Modify(notify::event::ModifyKind::Data(
+                    notify::event::DataChange::Content
+                ))
+            ) {
+                return None;
+            }
+            let path = event.paths.first()?.to_str()?.to_string();
+            return self.accept_path(ChangeKind::Modified, path);
+        }
+
+        if let notify::EventKind::Modify(notify::event::ModifyKind::Name(rename_mode)) =
+            event.kind
+        {
+            match rename_mode {
+                notify::event::RenameMode::Both => {
+                    if let [from, to] = event.paths.as_slice() {
+                        let from = from.to_str()?.to_string();
+                        let to = to.to_str()?.to_string();
+                        return self.accept_path(ChangeKind::Renamed { from, to: to.clone() }, to);
+                    }
+                }
+                notify::event::RenameMode::From => {
+                    if let (Some(cookie), Some(from)) = (event.tracker(), event.paths.first()) {
+                        pending_renames.insert(cookie, from.to_str()?.to_string());
+                    }
+                    return None;
+                }
+                notify::event::RenameMode::To => {
+                    let to = event.paths.first()?.to_str()?.to_string();
+                    if let Some(from) = event.tracker().and_then(|cookie| pending_renames.remove(&cookie)) {
+                        return self.accept_path(ChangeKind::Renamed { from, to: to.clone() }, to);
+                    }
+                    // No matching `From` was observed (e.g. the source was outside the watched
+                    // roots), so this looks like a new file from our vantage point.
+                    return self.accept_path(ChangeKind::Created, to);
+                }
+                _ => {}
+            }
+        }
+
+        let kind = match event.kind {
+            notify::EventKind::Create(_) => ChangeKind::Created,
+            notify::EventKind::Modify(notify::event::ModifyKind::Data(_)) => ChangeKind::Modified,
+            notify::EventKind::Remove(_) => ChangeKind::Removed,
+            _ => ChangeKind::Other,
+        };
+
+        let path = event.paths.first()?.to_str()?.to_string();
+        self.accept_path(kind, path)
+    }
+
+    /// Drop `path` if it matches an `ignore` pattern, otherwise pair it with `kind`
+    fn accept_path(&self, kind: ChangeKind, path: String) -> Option<(ChangeKind, String)> {
+        if self.ignore.iter().any(|pattern| pattern.matches(&path)) {
             return None;
         }
+        Some((kind, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> Monitor {
+        Monitor::new(".", 1000, |_, _| Ok(()))
+    }
+
+    #[test]
+    fn classifies_create_modify_and_remove_events() {
+        let monitor = monitor();
+        let mut pending_renames = HashMap::new();
+
+        let created = Event::new(notify::EventKind::Create(notify::event::CreateKind::File))
+            .add_path(PathBuf::from("/tmp/a.txt"));
+        assert_eq!(
+            monitor.get_valid_path(created, &mut pending_renames),
+            Some((ChangeKind::Created, "/tmp/a.txt".to_string()))
+        );
+
+        let modified = Event::new(notify::EventKind::Modify(
+            notify::event::ModifyKind::Data(notify::event::DataChange::Content),
+        ))
+        .add_path(PathBuf::from("/tmp/a.txt"));
+        assert_eq!(
+            monitor.get_valid_path(modified, &mut pending_renames),
+            Some((ChangeKind::Modified, "/tmp/a.txt".to_string()))
+        );
+
+        let removed = Event::new(notify::EventKind::Remove(notify::event::RemoveKind::File))
+            .add_path(PathBuf::from("/tmp/a.txt"));
+        assert_eq!(
+            monitor.get_valid_path(removed, &mut pending_renames),
+            Some((ChangeKind::Removed, "/tmp/a.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn content_only_drops_everything_but_content_modifications() {
+        let monitor = monitor().content_only();
+        let mut pending_renames = HashMap::new();
+
+        let created = Event::new(notify::EventKind::Create(notify::event::CreateKind::File))
+            .add_path(PathBuf::from("/tmp/a.txt"));
+        assert_eq!(monitor.get_valid_path(created, &mut pending_renames), None);
+
+        let modified = Event::new(notify::EventKind::Modify(
+            notify::event::ModifyKind::Data(notify::event::DataChange::Content),
+        ))
+        .add_path(PathBuf::from("/tmp/a.txt"));
+        assert_eq!(
+            monitor.get_valid_path(modified, &mut pending_renames),
+            Some((ChangeKind::Modified, "/tmp/a.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn ignore_patterns_drop_matching_paths() {
+        let monitor = monitor().ignore(&["**/target/**"]).unwrap();
+        let mut pending_renames = HashMap::new();
+
+        let ignored = Event::new(notify::EventKind::Create(notify::event::CreateKind::File))
+            .add_path(PathBuf::from("/repo/target/debug/out"));
+        assert_eq!(monitor.get_valid_path(ignored, &mut pending_renames), None);
+
+        let kept = Event::new(notify::EventKind::Create(notify::event::CreateKind::File))
+            .add_path(PathBuf::from("/repo/src/lib.rs"));
+        assert_eq!(
+            monitor.get_valid_path(kept, &mut pending_renames),
+            Some((ChangeKind::Created, "/repo/src/lib.rs".to_string()))
+        );
+    }
+
+    #[test]
+    fn coalesces_rename_from_and_to_events_sharing_a_tracker() {
+        let monitor = monitor();
+        let mut pending_renames = HashMap::new();
+
+        let from = Event::new(notify::EventKind::Modify(notify::event::ModifyKind::Name(
+            notify::event::RenameMode::From,
+        )))
+        .add_path(PathBuf::from("/tmp/old.txt"))
+        .set_tracker(42);
+        assert_eq!(monitor.get_valid_path(from, &mut pending_renames), None);
+
+        let to = Event::new(notify::EventKind::Modify(notify::event::ModifyKind::Name(
+            notify::event::RenameMode::To,
+        )))
+        .add_path(PathBuf::from("/tmp/new.txt"))
+        .set_tracker(42);
+        assert_eq!(
+            monitor.get_valid_path(to, &mut pending_renames),
+            Some((
+                ChangeKind::Renamed {
+                    from: "/tmp/old.txt".to_string(),
+                    to: "/tmp/new.txt".to_string(),
+                },
+                "/tmp/new.txt".to_string()
+            ))
+        );
+        assert!(pending_renames.is_empty());
+    }
+
+    #[test]
+    fn drain_batch_deduplicates_repeated_paths() {
+        let mut pending = HashMap::new();
+        pending.insert(PathBuf::from("/tmp/a.txt"), ChangeKind::Created);
+        pending.insert(PathBuf::from("/tmp/a.txt"), ChangeKind::Modified);
+        pending.insert(PathBuf::from("/tmp/b.txt"), ChangeKind::Modified);
+
+        let mut changes = drain_batch(&mut pending);
+        changes.sort_by(|a, b| a.1.cmp(&b.1));
 
-        event
-            .paths
-            .first()
-            .map(|path| path.to_str().unwrap().to_string())
+        assert_eq!(
+            changes,
+            vec![
+                (ChangeKind::Modified, "/tmp/a.txt".to_string()),
+                (ChangeKind::Modified, "/tmp/b.txt".to_string()),
+            ]
+        );
+        assert!(pending.is_empty());
     }
 }